@@ -109,11 +109,22 @@ mod reportes_view {
                 if let Some(orden) = self.marketplace.get_orden(i) {
                     // Consideramos solo órdenes completadas (Recibido)
                     if orden.estado == EstadoOrden::Recibido {
-                         let count = ventas_por_producto.entry(orden.id_producto).or_insert(0);
+                        // Las órdenes de carrito pueden incluir varios productos
+                        let mut lineas: Vec<(u32, u16)> = orden
+                            .items
+                            .iter()
+                            .map(|item| (item.id_producto, item.cant_producto))
+                            .collect();
+                        if lineas.is_empty() {
+                            lineas.push((orden.id_producto, orden.cant_producto));
+                        }
+
+                        for (id_producto, cant_producto) in lineas {
+                            let count = ventas_por_producto.entry(id_producto).or_insert(0);
                             *count = count
-                                .checked_add(orden.cant_producto as u32)
+                                .checked_add(cant_producto as u32)
                                 .unwrap_or(*count);
-
+                        }
                     }
                 }
             }
@@ -134,7 +145,7 @@ mod reportes_view {
         /// #Retorna
         /// -Vec<(Categoria, TotalVentas, CalificacionPromedio)>
         ///   -Categoria es la categoría del producto,
-        ///   -TotalVentas es la suma de los totales de órdenes (u128) en esa categoría,
+        ///   -TotalVentas es la suma de precio por cantidad (u128) de los productos vendidos en esa categoría,
         ///   -CalificacionPromedio es la reputación promedio (u8) de los vendedores en esa categoría.
         /// Simplificación: Promedio de reputación de vendedores únicos que tienen ventas en esa categoría.
         #[ink(message)]
@@ -157,7 +168,26 @@ mod reportes_view {
              for i in 0..cantidad_ordenes {
                  if let Some(orden) = self.marketplace.get_orden(i) {
                      if orden.estado != EstadoOrden::Cancelada {
-                         if let Some(producto) = self.marketplace.get_producto(orden.id_producto) {
+                         // Las órdenes de carrito pueden incluir productos de varias categorías
+                         let mut lineas: Vec<(u32, u128)> = orden
+                             .items
+                             .iter()
+                             .map(|item| {
+                                 let monto = item
+                                     .precio
+                                     .checked_mul(item.cant_producto as u128)
+                                     .unwrap_or(0);
+                                 (item.id_producto, monto)
+                             })
+                             .collect();
+                         if lineas.is_empty() {
+                             lineas.push((orden.id_producto, orden.total));
+                         }
+
+                         for (id_producto, monto) in lineas {
+                             let Some(producto) = self.marketplace.get_producto(id_producto) else {
+                                 continue;
+                             };
                              let categoria = producto.categoria;
                              
                              // Buscar si ya existe la categoría en stats
@@ -165,7 +195,7 @@ mod reportes_view {
                              for stat in &mut stats {
                                 if stat.0 == categoria {
                                     stat.1 = stat.1
-                                        .checked_add(orden.total)
+                                        .checked_add(monto)
                                         .unwrap_or(stat.1);
 
                                     if !stat.2.contains(&orden.vendedor) {
@@ -180,7 +210,7 @@ mod reportes_view {
                              if !found {
                                  let mut vendedores = Vec::new();
                                  vendedores.push(orden.vendedor);
-                                 stats.push((categoria, orden.total, vendedores));
+                                 stats.push((categoria, monto, vendedores));
                              }
                          }
                      }